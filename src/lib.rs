@@ -0,0 +1,15 @@
+
+//! A synthesiser library.
+
+#![crate_name = "synth"]
+
+extern crate rustc_serialize;
+
+pub mod env_point;
+pub mod envelope;
+pub mod gaussian;
+pub mod oscillator;
+pub mod pitch;
+pub mod waveform;
+
+mod wavetable;
@@ -8,6 +8,7 @@ use waveform::Waveform;
 
 pub type AmpEnvelope = Envelope<Point>;
 pub type FreqEnvelope = Envelope<Point>;
+pub type PanEnvelope = Envelope<Point>;
 
 /// The fundamental component of a synthesizer.
 #[derive(Debug, Clone, RustcEncodable, RustcDecodable)]
@@ -21,6 +22,18 @@ pub struct Oscillator {
     pub amplitude: AmpEnvelope,
     /// Envelope for interpolation of frequency.
     pub frequency: FreqEnvelope,
+    /// Optional modulator used for phase (frequency) modulation.
+    pub modulator: Option<Box<Oscillator>>,
+    /// Depth of the phase modulation applied by the modulator.
+    pub mod_index: f32,
+    /// Whether to render with PolyBLEP band-limiting to suppress aliasing.
+    pub band_limited: bool,
+    /// Phase-warp control: a pivot point in `[0,1)` and a bend amount.
+    pub bend: (f64, f64),
+    /// Harmonic partials as `(frequency ratio, gain)` pairs summed per sample.
+    pub harmonics: Vec<(f32, f32)>,
+    /// Envelope for interpolation of stereo pan position in `[-1, 1]`.
+    pub pan: PanEnvelope,
 }
 
 impl Oscillator {
@@ -34,6 +47,12 @@ impl Oscillator {
             amplitude: Envelope::zeroed(),
             frequency: Envelope::zeroed(),
             gaussian_perc: 0.0,
+            modulator: None,
+            mod_index: 0.0,
+            band_limited: false,
+            bend: (0.5, 0.0),
+            harmonics: vec![(1.0, 1.0)],
+            pan: Envelope::zeroed(),
         }
     }
 
@@ -55,6 +74,12 @@ impl Oscillator {
         Oscillator { frequency: freq_env, ..self }
     }
 
+    /// Pan envelope builder method.
+    #[inline]
+    pub fn pan(self, pan_env: PanEnvelope) -> Oscillator {
+        Oscillator { pan: pan_env, ..self }
+    }
+
     /// Set a gaussian randomness to the frequency envelope value retrieval
     /// for a "warbly" effect.
     #[inline]
@@ -62,14 +87,126 @@ impl Oscillator {
         Oscillator { gaussian_perc: warbliness, ..self }
     }
 
+    /// Phase-modulation builder method. Drive this (carrier) oscillator with
+    /// a modulator oscillator at the given modulation index.
+    #[inline]
+    pub fn fm(self, modulator: Oscillator, index: f32) -> Oscillator {
+        Oscillator { modulator: Some(Box::new(modulator)), mod_index: index, ..self }
+    }
+
+    /// Opt in to PolyBLEP band-limited rendering for anti-aliased square and
+    /// sawtooth output.
+    #[inline]
+    pub fn band_limited(self, band_limited: bool) -> Oscillator {
+        Oscillator { band_limited: band_limited, ..self }
+    }
+
+    /// Phase-bend builder method. Warps the phase about `pivot` by `amount`,
+    /// morphing the waveform toward saw-/pulse-like shapes.
+    #[inline]
+    pub fn phase_bend(self, pivot: f64, amount: f64) -> Oscillator {
+        Oscillator { bend: (pivot, amount), ..self }
+    }
+
+    /// Harmonic stack builder method.
+    #[inline]
+    pub fn harmonics(self, harmonics: Vec<(f32, f32)>) -> Oscillator {
+        Oscillator { harmonics: harmonics, ..self }
+    }
+
+    /// Fill the first `n` integer harmonics, each harmonic `h` with gain `1/h`,
+    /// for a sawtooth-like additive spectrum.
+    #[inline]
+    pub fn saw_harmonics(self, n: u32) -> Oscillator {
+        let harmonics = (1..n + 1)
+            .map(|h| (h as f32, 1.0 / h as f32))
+            .collect();
+        Oscillator { harmonics: harmonics, ..self }
+    }
+
+    /// Fill the first `n` odd integer harmonics, each harmonic `k` with gain
+    /// `1/k`, for a square-like additive spectrum.
+    #[inline]
+    pub fn square_harmonics(self, n: u32) -> Oscillator {
+        let harmonics = (1..n + 1)
+            .map(|h| { let k = 2 * h - 1; (k as f32, 1.0 / k as f32) })
+            .collect();
+        Oscillator { harmonics: harmonics, ..self }
+    }
+
+    /// Remap a phase through the two-segment piecewise-linear bend curve,
+    /// compressing the cycle before the pivot and stretching it after (or vice
+    /// versa, depending on the sign of the bend amount).
+    #[inline]
+    fn bend_phase(&self, phase: f64) -> f64 {
+        let (pivot, amount) = self.bend;
+        if amount == 0.0 {
+            return phase;
+        }
+        let whole = phase.floor();
+        let frac = phase - whole;
+        // Clamp the pivot away from the edges so neither segment degenerates.
+        let x0 = pivot.min(1.0 - 1e-6).max(1e-6);
+        let y0 = (pivot + amount).min(1.0).max(0.0);
+        let warped = if frac < x0 {
+            frac / x0 * y0
+        } else {
+            y0 + (frac - x0) / (1.0 - x0) * (1.0 - y0)
+        };
+        whole + warped
+    }
+
     /// Calculate and return the amplitude at the given ratio.
     #[inline]
     pub fn amp_at_ratio(&mut self, ratio: f64, note_freq_multi: f64, sample_hz: f64) -> f32 {
-        let phase = self.phase;
+        self.sample_at_ratio(ratio, note_freq_multi, sample_hz) * self.amplitude.y(ratio) as f32
+    }
+
+    /// Calculate the raw (pre-amplitude-envelope) waveform sample at the given
+    /// ratio, advancing the phase. This is the signal used to drive a carrier's
+    /// phase modulation, so the `mod_index` takes effect without the caller
+    /// having to set a non-zero modulator amplitude envelope.
+    #[inline]
+    fn sample_at_ratio(&mut self, ratio: f64, note_freq_multi: f64, sample_hz: f64) -> f32 {
         let freq_at_ratio = self.freq_at_ratio(ratio) * note_freq_multi;
+        // Evaluate the modulator (if any) at the same ratio, advancing its own
+        // phase so that carrier->modulator chains compose recursively.
+        let m = match self.modulator {
+            Some(ref mut modulator) => modulator.sample_at_ratio(ratio, note_freq_multi, sample_hz) as f64,
+            None => 0.0,
+        };
         // Determine the next phase with respect to frequency and sample rate.
-        self.phase = phase + (freq_at_ratio / sample_hz);
-        self.waveform.amp_at_phase(phase) * self.amplitude.y(ratio) as f32
+        let dt = freq_at_ratio / sample_hz;
+        self.phase += dt;
+        let base = self.bend_phase(self.phase) + self.mod_index as f64 * m;
+        // Sum the harmonic partials, advancing the fundamental phase only once.
+        // Request anti-aliased output when band-limiting is enabled, otherwise
+        // take the naive sample.
+        let mut total = 0.0;
+        let mut norm = 0.0;
+        for &(harm_ratio, gain) in self.harmonics.iter() {
+            let phase = base * harm_ratio as f64;
+            let partial = if self.band_limited {
+                self.waveform.amp_at_phase_blep(phase, dt * harm_ratio as f64)
+            } else {
+                self.waveform.amp_at_phase(phase)
+            };
+            total += partial * gain;
+            norm += gain;
+        }
+        // Normalize so the summed amplitude stays bounded regardless of partial count.
+        if norm > 0.0 { total / norm } else { total }
+    }
+
+    /// Calculate the amplitude at the given ratio and place it in the stereo
+    /// field using an equal-power pan law, returning the `(left, right)` pair.
+    #[inline]
+    pub fn stereo_amp_at_ratio(&mut self, ratio: f64, note_freq_multi: f64, sample_hz: f64) -> (f32, f32) {
+        use std::f64::consts::FRAC_PI_2;
+        let sample = self.amp_at_ratio(ratio, note_freq_multi, sample_hz);
+        // Map the pan position in [-1, 1] onto an equal-power left/right gain.
+        let angle = (self.pan.y(ratio) + 1.0) / 2.0 * FRAC_PI_2;
+        (sample * angle.cos() as f32, sample * angle.sin() as f32)
     }
 
     /// Calculate and return the frequency at
@@ -0,0 +1,71 @@
+
+//! Waveform module.
+
+use gaussian;
+use wavetable;
+
+/// Represents the "shape" used to oscillate a signal over a phase.
+#[derive(Debug, Clone, Copy, PartialEq, RustcEncodable, RustcDecodable)]
+pub enum Waveform {
+    /// Sinusoidal wave.
+    Sine,
+    /// Sawtooth wave.
+    Saw,
+    /// Square wave.
+    Square,
+    /// White noise.
+    Noise,
+    /// Sinusoidal wave played at a rescaled envelope frequency.
+    NoiseWalk,
+}
+
+impl Waveform {
+
+    /// Return the amplitude of the waveform at the given phase (in cycles).
+    #[inline]
+    pub fn amp_at_phase(&self, phase: f64) -> f32 {
+        use std::f64::consts::PI;
+        let t = phase - phase.floor();
+        match *self {
+            Waveform::Sine | Waveform::NoiseWalk => wavetable::fast_sin(phase * 2.0 * PI),
+            Waveform::Saw => (2.0 * t - 1.0) as f32,
+            Waveform::Square => if t < 0.5 { 1.0 } else { -1.0 },
+            Waveform::Noise => gaussian::gen(0.0, 1.0),
+        }
+    }
+
+    /// Return the band-limited amplitude of the waveform at the given phase,
+    /// applying PolyBLEP correction at the discontinuities of the square and
+    /// sawtooth shapes. `dt` is the per-sample phase increment in cycles.
+    /// Continuous shapes fall back to the naive sample.
+    #[inline]
+    pub fn amp_at_phase_blep(&self, phase: f64, dt: f64) -> f32 {
+        let t = phase - phase.floor();
+        match *self {
+            Waveform::Saw => (2.0 * t - 1.0 - poly_blep(t, dt)) as f32,
+            Waveform::Square => {
+                let naive = if t < 0.5 { 1.0 } else { -1.0 };
+                (naive + poly_blep(t, dt) - poly_blep((t + 0.5) % 1.0, dt)) as f32
+            },
+            _ => self.amp_at_phase(phase),
+        }
+    }
+
+}
+
+/// PolyBLEP residual used to round the discontinuities of naive waveforms,
+/// suppressing the aliasing they would otherwise produce.
+#[inline]
+fn poly_blep(t: f64, dt: f64) -> f64 {
+    if dt <= 0.0 {
+        0.0
+    } else if t < dt {
+        let x = t / dt;
+        2.0 * x - x * x - 1.0
+    } else if t > 1.0 - dt {
+        let x = (t - 1.0) / dt;
+        x * x + 2.0 * x + 1.0
+    } else {
+        0.0
+    }
+}